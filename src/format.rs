@@ -0,0 +1,82 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl OutputFormat {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "human" => Some(Self::Human),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+pub fn extract(args: Vec<String>) -> Result<(OutputFormat, Vec<String>), String> {
+    let mut rest = Vec::with_capacity(args.len());
+    let mut format = OutputFormat::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--format=") {
+            format = OutputFormat::parse(value).ok_or_else(|| value.to_string())?;
+        } else if arg == "--format" {
+            let value = args.next().unwrap_or_default();
+            format = OutputFormat::parse(&value).ok_or_else(|| value.clone())?;
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((format, rest))
+}
+
+pub fn string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+pub fn array(items: impl IntoIterator<Item = String>) -> String {
+    let items: Vec<String> = items.into_iter().collect();
+    format!("[{}]", items.join(","))
+}
+
+pub fn object(fields: &[(&str, String)]) -> String {
+    let parts: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{value}", string(key)))
+        .collect();
+    format!("{{{}}}", parts.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_escapes_quotes_and_backslashes() {
+        assert_eq!(string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(string(r"back\slash"), r#""back\\slash""#);
+    }
+
+    #[test]
+    fn string_escapes_newlines() {
+        assert_eq!(string("a\nb"), r#""a\nb""#);
+    }
+
+    #[test]
+    fn string_passes_through_plain_text() {
+        assert_eq!(string("alice"), r#""alice""#);
+    }
+}
@@ -0,0 +1,22 @@
+pub const PREPARE_COMMIT_MSG: &str = r#"#!/bin/sh
+# Installed by `author install-hook`. Do not edit by hand; re-run
+# `author install-hook` to update.
+msg_file="$1"
+
+trailers="$(author trailers 2>/dev/null)" || exit 0
+[ -z "$trailers" ] && exit 0
+
+missing=""
+while IFS= read -r trailer; do
+    [ -z "$trailer" ] && continue
+    if ! grep -qF "$trailer" "$msg_file"; then
+        missing="$missing$trailer
+"
+    fi
+done <<EOF
+$trailers
+EOF
+
+[ -z "$missing" ] && exit 0
+printf '\n%s' "$missing" >> "$msg_file"
+"#;
@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+pub const REGISTRY_FILE_NAME: &str = "author.idents";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+}
+
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+pub fn resolve(login: &str, repo_root: Option<&Path>) -> Identity {
+    load_registry(repo_root)
+        .remove(login)
+        .unwrap_or_else(|| fallback(login))
+}
+
+fn fallback(login: &str) -> Identity {
+    Identity {
+        name: login.to_string(),
+        email: format!("{login}@users.noreply.github.com"),
+    }
+}
+
+// Per-repo entries override global ones for the same key.
+pub fn load_registry(repo_root: Option<&Path>) -> BTreeMap<String, Identity> {
+    let mut registry = BTreeMap::new();
+    if let Some(path) = global_registry_path() {
+        merge_file(&path, &mut registry);
+    }
+    if let Some(root) = repo_root {
+        merge_file(&root.join(REGISTRY_FILE_NAME), &mut registry);
+    }
+    registry
+}
+
+pub fn register(repo_root: &Path, key: &str, identity: &Identity) -> std::io::Result<()> {
+    let path = repo_root.join(REGISTRY_FILE_NAME);
+    let mut entries = BTreeMap::new();
+    merge_file(&path, &mut entries);
+    entries.insert(key.to_string(), identity.clone());
+    write_entries(&path, &entries)
+}
+
+pub fn parse_identity(value: &str) -> Option<Identity> {
+    let (name, email) = value.trim().rsplit_once(" <")?;
+    let email = email.strip_suffix('>')?;
+    Some(Identity {
+        name: name.trim().to_string(),
+        email: email.trim().to_string(),
+    })
+}
+
+fn global_registry_path() -> Option<PathBuf> {
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("author").join("idents.toml"))
+}
+
+fn merge_file(path: &Path, registry: &mut BTreeMap<String, Identity>) {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return;
+    };
+    for line in contents.lines() {
+        if let Some((key, identity)) = parse_entry(line.trim()) {
+            registry.insert(key, identity);
+        }
+    }
+}
+
+fn write_entries(path: &Path, entries: &BTreeMap<String, Identity>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::File::create(path)?;
+    for (key, identity) in entries {
+        writeln!(file, "{key} = \"{identity}\"")?;
+    }
+    Ok(())
+}
+
+fn parse_entry(line: &str) -> Option<(String, Identity)> {
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (key, value) = line.split_once('=')?;
+    let identity = parse_identity(value.trim().trim_matches('"'))?;
+    Some((key.trim().to_string(), identity))
+}
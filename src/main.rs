@@ -5,6 +5,14 @@ use std::path::{Path, PathBuf};
 
 use colored::Colorize as _;
 
+mod format;
+mod hooks;
+mod identity;
+mod status;
+mod verify;
+
+use format::OutputFormat;
+
 type Result<T> = std::result::Result<T, AuthorError>;
 
 #[derive(Debug, thiserror::Error)]
@@ -21,6 +29,40 @@ enum AuthorError {
     Io(#[from] std::io::Error),
     #[error("No authors in list")]
     NoAuthors,
+    #[error("No identities registered")]
+    NoIdents,
+    #[error("register requires a key and a \"Name <email>\" identity")]
+    MissingRegistration,
+    #[error("invalid identity {0:?}, expected \"Name <email>\"")]
+    InvalidIdentity(String),
+    #[error("unknown format {0:?}, expected \"human\" or \"json\"")]
+    UnknownFormat(String),
+    #[error("{} already exists and isn't author's hook; remove it or merge manually", .0.display())]
+    HookExists(PathBuf),
+    #[error(
+        "--format is the global human/json output mode; status's template string is --template {0:?}"
+    )]
+    StatusTemplateFlag(String),
+}
+
+impl AuthorError {
+    /// The process exit code for this error, so scripts can branch on
+    /// failure mode instead of just "non-zero".
+    fn exit_code(&self) -> i32 {
+        match self {
+            Self::NotInRepo => 2,
+            Self::UnknownCommand(_) => 3,
+            Self::MissingLogins => 4,
+            Self::NoAuthors => 5,
+            Self::NoIdents => 6,
+            Self::MissingRegistration => 7,
+            Self::InvalidIdentity(_) => 8,
+            Self::UnknownFormat(_) => 9,
+            Self::HookExists(_) => 10,
+            Self::StatusTemplateFlag(_) => 11,
+            Self::Inquire(_) | Self::Io(_) => 1,
+        }
+    }
 }
 
 enum Directory {
@@ -32,15 +74,25 @@ enum Directory {
 struct Report {
     removed: Vec<String>,
     added: Vec<String>,
+    added_from_registry: Vec<(String, identity::Identity)>,
     not_added: Vec<String>,
     not_removed: Vec<String>,
+    maybe_typos: Vec<(String, Option<String>)>,
 }
 
 impl Report {
+    fn maybe_typo(&mut self, login: String, suggestion: Option<String>) {
+        self.maybe_typos.push((login, suggestion));
+    }
+
     fn added(&mut self, value: String) {
         self.added.push(value);
     }
 
+    fn added_from_registry(&mut self, key: String, resolved: identity::Identity) {
+        self.added_from_registry.push((key, resolved));
+    }
+
     fn not_added(&mut self, value: String) {
         self.not_added.push(value);
     }
@@ -58,51 +110,143 @@ struct AuthorManager {
     directory: Directory,
     file: PathBuf,
     report: Report,
+    format: OutputFormat,
 }
 
-impl std::fmt::Display for Report {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Report {
+    /// Writes this report to `out` in the given format. JSON mode
+    /// suppresses all color/italic human formatting entirely.
+    fn write(&self, out: &mut impl std::io::Write, format: OutputFormat) -> std::io::Result<()> {
+        match format {
+            OutputFormat::Human => self.write_human(out),
+            OutputFormat::Json => writeln!(out, "{}", self.to_json()),
+        }
+    }
+
+    fn write_human(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
         if !self.removed.is_empty() {
-            writeln!(f, "{}", format!("-- {}", self.removed.join(", ")).red())?;
+            writeln!(out, "{}", format!("-- {}", self.removed.join(", ")).red())?;
         }
         if !self.added.is_empty() {
-            writeln!(f, "{}", format!("++ {}", self.added.join(", ")).green())?;
+            writeln!(out, "{}", format!("++ {}", self.added.join(", ")).green())?;
+        }
+        if !self.added_from_registry.is_empty() {
+            let entries: Vec<String> = self
+                .added_from_registry
+                .iter()
+                .map(|(key, identity)| format!("{key} ({identity})"))
+                .collect();
+            writeln!(out, "{}", format!("++ {}", entries.join(", ")).green())?;
         }
         if !self.not_removed.is_empty() {
             writeln!(
-                f,
+                out,
                 "did not remove {} (did not exist)",
                 self.not_removed.join(", ")
             )?;
         }
         if !self.not_added.is_empty() {
             writeln!(
-                f,
+                out,
                 "did not add {} (already existed)",
                 self.not_added.join(", ")
             )?;
         }
+        for (login, suggestion) in &self.maybe_typos {
+            let note = match suggestion {
+                Some(suggestion) => format!("?? {login}: not in git history, did you mean {suggestion}?"),
+                None => format!("?? {login}: not in git history"),
+            };
+            writeln!(out, "{}", note.yellow())?;
+        }
         Ok(())
     }
+
+    fn to_json(&self) -> String {
+        let added: Vec<String> = self
+            .added
+            .iter()
+            .chain(self.added_from_registry.iter().map(|(key, _)| key))
+            .map(|login| format::string(login))
+            .collect();
+        let maybe_typos = self.maybe_typos.iter().map(|(login, suggestion)| {
+            format::object(&[
+                ("login", format::string(login)),
+                (
+                    "suggestion",
+                    suggestion
+                        .as_deref()
+                        .map(format::string)
+                        .unwrap_or_else(|| "null".to_string()),
+                ),
+            ])
+        });
+        format::object(&[
+            ("added", format::array(added)),
+            (
+                "removed",
+                format::array(self.removed.iter().map(|login| format::string(login))),
+            ),
+            (
+                "not_added",
+                format::array(self.not_added.iter().map(|login| format::string(login))),
+            ),
+            (
+                "not_removed",
+                format::array(self.not_removed.iter().map(|login| format::string(login))),
+            ),
+            ("maybe_typos", format::array(maybe_typos)),
+        ])
+    }
 }
 
 fn main() {
-    if let Err(err) = run("author.txt") {
-        eprintln!("{}", format!("{err}").red());
-    }
+    let result = run("author.txt");
+    let code = match &result {
+        Ok(()) => 0,
+        // In human mode the hint is already on stderr, so the error is not
+        // printed again; in JSON mode this case writes nothing to stderr.
+        Err(AuthorError::NoAuthors) => AuthorError::NoAuthors.exit_code(),
+        Err(err) => {
+            eprintln!("{}", format!("{err}").red());
+            err.exit_code()
+        }
+    };
+    std::process::exit(code);
 }
 
 fn run(author_file_name: &str) -> Result<()> {
-    let mut args = std::env::args();
-    let program = args.next().unwrap_or_else(|| "author".to_string());
+    let mut raw_args = std::env::args();
+    let program = raw_args.next().unwrap_or_else(|| "author".to_string());
+    let remaining: Vec<String> = raw_args.collect();
+    let targets_status = remaining.iter().any(|arg| arg == "status");
+    let (format, rest) = format::extract(remaining).map_err(|value| {
+        if targets_status {
+            AuthorError::StatusTemplateFlag(value)
+        } else {
+            AuthorError::UnknownFormat(value)
+        }
+    })?;
+    let mut args = rest.into_iter();
     let command = args.next();
+    // `status` is a prompt segment and `trailers` is consumed verbatim by
+    // the installed git hook: neither can tolerate the banner line mixed
+    // into their stdout.
+    let is_quiet_command = matches!(command.as_deref(), Some("status") | Some("trailers"));
     let mut author_manager =
         AuthorManager::find_author_file(std::env::current_dir()?, author_file_name)?;
-    println!("operating {}", author_manager);
+    author_manager.format = format;
+    if format == OutputFormat::Human && !is_quiet_command {
+        println!("operating {}", author_manager);
+    }
 
     let cmd_res = match command.as_deref() {
         None => author_manager.list_authors(),
-        Some("add") => author_manager.add_authors(args.collect()),
+        Some("add") => {
+            let (verify, logins) = verify::extract_flag(args.collect());
+            author_manager.add_authors(logins, verify)
+        }
+        Some("status") => author_manager.status(args.collect()),
         Some("remove") => {
             let removals: Vec<String> = args.collect();
             if removals.is_empty() {
@@ -111,15 +255,20 @@ fn run(author_file_name: &str) -> Result<()> {
                 author_manager.remove_authors(removals)
             }
         }
+        Some("trailers") => author_manager.print_trailers(),
+        Some("install-hook") => author_manager.install_hook(),
+        Some("register") => author_manager.register_identity(args.collect()),
+        Some("known") | Some("list-idents") => author_manager.list_idents(),
         Some(other) => Err(AuthorError::UnknownCommand(other.to_string())),
     };
-    print!("{}", author_manager.report);
-    if let Err(AuthorError::NoAuthors) = cmd_res {
+    if !is_quiet_command {
+        let mut stdout = std::io::stdout();
+        author_manager.report.write(&mut stdout, format)?;
+    }
+    if matches!(cmd_res, Err(AuthorError::NoAuthors)) && format == OutputFormat::Human {
         no_authors_message(&program);
-        Ok(())
-    } else {
-        cmd_res
     }
+    cmd_res
 }
 
 impl std::fmt::Display for AuthorManager {
@@ -138,6 +287,7 @@ impl AuthorManager {
             directory,
             file,
             report: Report::default(),
+            format: OutputFormat::default(),
         }
     }
     fn find_author_file(start: PathBuf, author_file_name: &str) -> Result<Self> {
@@ -165,22 +315,72 @@ impl AuthorManager {
         if authors.is_empty() {
             return Err(AuthorError::NoAuthors);
         }
-        for author in authors {
-            println!("{author}");
+        let mut stdout = std::io::stdout();
+        match self.format {
+            OutputFormat::Human => {
+                for author in &authors {
+                    writeln!(stdout, "{author}")?;
+                }
+            }
+            OutputFormat::Json => {
+                let registry = identity::load_registry(self.repo_root());
+                let objects = authors.iter().map(|login| {
+                    let identity = registry
+                        .get(login)
+                        .map(|identity| {
+                            format::object(&[
+                                ("name", format::string(&identity.name)),
+                                ("email", format::string(&identity.email)),
+                            ])
+                        })
+                        .unwrap_or_else(|| "null".to_string());
+                    format::object(&[("login", format::string(login)), ("identity", identity)])
+                });
+                writeln!(stdout, "{}", format::array(objects))?;
+            }
         }
         Ok(())
     }
 
-    fn add_authors(&mut self, logins: Vec<String>) -> Result<()> {
+    fn add_authors(&mut self, logins: Vec<String>, verify: bool) -> Result<()> {
         if logins.is_empty() {
             return Err(AuthorError::MissingLogins);
         }
         let mut authors = read_authors(&self.file)?;
+        let registry = identity::load_registry(self.repo_root());
+        let history = match (verify, self.repo_root()) {
+            (true, Some(root)) => verify::historical_identities(root),
+            _ => Vec::new(),
+        };
         for login in logins {
-            if authors.insert(login.clone()) {
-                self.report.added(login);
-            } else {
+            if !authors.insert(login.clone()) {
                 self.report.not_added(login);
+                continue;
+            }
+            let resolved = registry.get(&login).cloned();
+            match &resolved {
+                Some(identity) => self.report.added_from_registry(login.clone(), identity.clone()),
+                None => self.report.added(login.clone()),
+            }
+            if !verify || history.is_empty() {
+                // No history to compare against (new repo, no commits
+                // yet, or not in a git repo at all) - nothing to flag.
+                continue;
+            }
+            let known = match &resolved {
+                Some(identity) => {
+                    verify::is_known(&identity.name, &history)
+                        || verify::is_known(&identity.email, &history)
+                }
+                None => verify::is_known(&login, &history),
+            };
+            if !known {
+                let subject = resolved
+                    .as_ref()
+                    .map(|identity| identity.name.as_str())
+                    .unwrap_or(&login);
+                let suggestion = verify::suggest(subject, &history).map(str::to_string);
+                self.report.maybe_typo(login, suggestion);
             }
         }
         write_authors(&self.file, &authors)
@@ -198,6 +398,81 @@ impl AuthorManager {
         write_authors(&self.file, &authors)
     }
 
+    fn repo_root(&self) -> Option<&Path> {
+        match &self.directory {
+            Directory::GitRepo(path) => Some(path.as_path()),
+            Directory::Bare => None,
+        }
+    }
+
+    fn print_trailers(&self) -> Result<()> {
+        let authors = read_authors(&self.file)?;
+        if authors.is_empty() {
+            return Err(AuthorError::NoAuthors);
+        }
+        let repo_root = self.repo_root();
+        for login in &authors {
+            let identity = identity::resolve(login, repo_root);
+            println!("Co-authored-by: {identity}");
+        }
+        Ok(())
+    }
+
+    fn install_hook(&self) -> Result<()> {
+        let Directory::GitRepo(root) = &self.directory else {
+            return Err(AuthorError::NotInRepo);
+        };
+        let hooks_dir = root.join(".git").join("hooks");
+        std::fs::create_dir_all(&hooks_dir)?;
+        let hook_path = hooks_dir.join("prepare-commit-msg");
+        // Don't clobber someone else's hook (husky, pre-commit, a
+        // hand-written script); only (re)write our own, recognised by
+        // the header line that every version of it carries.
+        if let Ok(existing) = std::fs::read_to_string(&hook_path) {
+            if !existing.contains("# Installed by `author install-hook`.") {
+                return Err(AuthorError::HookExists(hook_path));
+            }
+        }
+        std::fs::write(&hook_path, hooks::PREPARE_COMMIT_MSG)?;
+        make_executable(&hook_path)?;
+        println!("installed prepare-commit-msg hook at {}", hook_path.display());
+        Ok(())
+    }
+
+    fn register_identity(&mut self, args: Vec<String>) -> Result<()> {
+        let repo_root = self.repo_root().ok_or(AuthorError::NotInRepo)?;
+        let mut args = args.into_iter();
+        let key = args.next().ok_or(AuthorError::MissingRegistration)?;
+        let identity_str = args.collect::<Vec<String>>().join(" ");
+        let identity = identity::parse_identity(&identity_str)
+            .ok_or_else(|| AuthorError::InvalidIdentity(identity_str.clone()))?;
+        identity::register(repo_root, &key, &identity)?;
+        println!("registered {key} = {identity}");
+        Ok(())
+    }
+
+    fn list_idents(&self) -> Result<()> {
+        let registry = identity::load_registry(self.repo_root());
+        if registry.is_empty() {
+            return Err(AuthorError::NoIdents);
+        }
+        for (key, identity) in &registry {
+            println!("{key} = {identity}");
+        }
+        Ok(())
+    }
+
+    fn status(&self, args: Vec<String>) -> Result<()> {
+        let options = status::parse(args);
+        let authors = read_authors(&self.file)?;
+        if authors.is_empty() && !options.show_empty {
+            return Ok(());
+        }
+        let logins: Vec<String> = authors.into_iter().collect();
+        println!("{}", status::render(&options, &logins));
+        Ok(())
+    }
+
     fn prompt_remove(&mut self) -> Result<()> {
         let authors = read_authors(&self.file)?;
         if authors.is_empty() {
@@ -212,11 +487,25 @@ impl AuthorManager {
     }
 }
 
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt as _;
+    let mut perms = std::fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o755);
+    std::fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
 fn no_authors_message(program_name: &str) {
     let prefix = "no authors specified, run ".italic();
     let command = format!("{program_name} add login").bold().italic();
     let suffix = " to add them".italic();
-    println!("{prefix}{command}{suffix}");
+    eprintln!("{prefix}{command}{suffix}");
 }
 
 fn read_authors(path: &Path) -> Result<BTreeSet<String>> {
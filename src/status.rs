@@ -0,0 +1,74 @@
+pub const DEFAULT_SYMBOL: &str = "co";
+
+pub struct Options {
+    pub show_empty: bool,
+    pub symbol: String,
+    pub limit: Option<usize>,
+    pub template: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            show_empty: false,
+            symbol: DEFAULT_SYMBOL.to_string(),
+            limit: None,
+            template: None,
+        }
+    }
+}
+
+// `--template` on purpose, not `--format`: that flag is already the
+// global human/json output mode, and this takes an arbitrary
+// `{count}`/`{authors}` string instead.
+pub fn parse(args: Vec<String>) -> Options {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--show-empty" => options.show_empty = true,
+            "--symbol" => options.symbol = args.next().unwrap_or_default(),
+            "--limit" => options.limit = args.next().and_then(|value| value.parse().ok()),
+            "--template" => options.template = args.next(),
+            _ => {}
+        }
+    }
+    options
+}
+
+pub fn render(options: &Options, logins: &[String]) -> String {
+    let count = logins.len();
+    let Some(template) = &options.template else {
+        return format!("{} {count}", options.symbol);
+    };
+    let authors = match options.limit {
+        Some(limit) => logins.iter().take(limit).cloned().collect::<Vec<_>>().join(", "),
+        None => logins.join(", "),
+    };
+    template
+        .replace("{count}", &count.to_string())
+        .replace("{authors}", &authors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_render_is_symbol_and_count() {
+        let options = Options::default();
+        let logins = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(render(&options, &logins), "co 2");
+    }
+
+    #[test]
+    fn template_substitutes_count_and_limited_authors() {
+        let options = Options {
+            template: Some("{count}: {authors}".to_string()),
+            limit: Some(1),
+            ..Options::default()
+        };
+        let logins = vec!["alice".to_string(), "bob".to_string()];
+        assert_eq!(render(&options, &logins), "2: alice");
+    }
+}
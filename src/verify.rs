@@ -0,0 +1,100 @@
+use std::path::Path;
+use std::process::Command;
+
+pub fn extract_flag(args: Vec<String>) -> (bool, Vec<String>) {
+    let mut verify = false;
+    let mut rest = Vec::with_capacity(args.len());
+    for arg in args {
+        if arg == "--verify" {
+            verify = true;
+        } else {
+            rest.push(arg);
+        }
+    }
+    (verify, rest)
+}
+
+pub fn historical_identities(repo_root: &Path) -> Vec<String> {
+    let Ok(output) = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(["log", "--all", "--format=%an%n%ae%n%cn%n%ce"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut seen = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if !line.is_empty() && !seen.iter().any(|s: &String| s == line) {
+            seen.push(line.to_string());
+        }
+    }
+    seen
+}
+
+pub fn is_known(login: &str, history: &[String]) -> bool {
+    history.iter().any(|candidate| candidate.eq_ignore_ascii_case(login))
+}
+
+// Prefix match wins over edit distance when both are available.
+pub fn suggest<'a>(login: &str, history: &'a [String]) -> Option<&'a str> {
+    let lower = login.to_lowercase();
+    history
+        .iter()
+        .find(|candidate| candidate.to_lowercase().starts_with(&lower))
+        .or_else(|| {
+            history
+                .iter()
+                .min_by_key(|candidate| edit_distance(&lower, &candidate.to_lowercase()))
+        })
+        .map(String::as_str)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_distance_counts_substitutions() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn suggest_prefers_prefix_match_over_closer_edit_distance() {
+        let history = vec!["alix".to_string(), "alice wonderland".to_string()];
+        assert_eq!(suggest("alic", &history), Some("alice wonderland"));
+    }
+
+    #[test]
+    fn suggest_falls_back_to_edit_distance_without_a_prefix_match() {
+        let history = vec!["bob".to_string(), "alice".to_string()];
+        assert_eq!(suggest("alce", &history), Some("alice"));
+    }
+
+    #[test]
+    fn suggest_returns_none_for_empty_history() {
+        assert_eq!(suggest("anyone", &[]), None);
+    }
+}